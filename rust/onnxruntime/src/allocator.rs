@@ -0,0 +1,30 @@
+//! Module containing the process-wide default allocator used for calls that don't bind to a
+//! caller-supplied buffer (e.g. reading variable-length metadata strings).
+
+use lazy_static::lazy_static;
+use onnxruntime_sys as sys;
+
+use crate::g_ort;
+
+lazy_static! {
+    static ref DEFAULT_ALLOCATOR: DefaultAllocator = {
+        let mut allocator_ptr: *mut sys::OrtAllocator = std::ptr::null_mut();
+        let status =
+            unsafe { g_ort().GetAllocatorWithDefaultOptions.unwrap()(&mut allocator_ptr) };
+        assert!(status.is_null(), "GetAllocatorWithDefaultOptions failed");
+        DefaultAllocator(allocator_ptr)
+    };
+}
+
+/// The default allocator is owned by the runtime itself and never released by callers, so it's
+/// safe to share a single instance across threads for the lifetime of the process.
+struct DefaultAllocator(*mut sys::OrtAllocator);
+
+unsafe impl Send for DefaultAllocator {}
+unsafe impl Sync for DefaultAllocator {}
+
+/// The process-wide default `OrtAllocator`, usable for any call that needs an allocator but
+/// doesn't care which one.
+pub(crate) fn default_allocator() -> *mut sys::OrtAllocator {
+    DEFAULT_ALLOCATOR.0
+}
@@ -1,9 +1,9 @@
 //! Module containing environment types
 
 use crate::{
+    custom_logger,
     error::{status_to_result, OrtError, Result},
     g_ort,
-    onnxruntime::custom_logger,
     session::SessionBuilder,
     LoggingLevel,
 };
@@ -0,0 +1,194 @@
+//! Module containing error types
+
+use std::ffi::CStr;
+
+use onnxruntime_sys as sys;
+
+use crate::g_ort;
+
+/// Type alias used across the crate for fallible ONNX Runtime operations.
+pub type Result<T> = std::result::Result<T, OrtError>;
+
+/// The error message extracted from a non-null `OrtStatus` returned by the C API.
+#[derive(Debug, Clone)]
+pub struct OrtApiError {
+    message: String,
+}
+
+impl OrtApiError {
+    #[cfg(test)]
+    pub(crate) fn new(message: impl Into<String>) -> Self {
+        OrtApiError {
+            message: message.into(),
+        }
+    }
+
+    /// The raw error message reported by the ONNX Runtime C API.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl std::fmt::Display for OrtApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Errors produced by this crate.
+#[derive(Debug, thiserror::Error)]
+pub enum OrtError {
+    /// Could not create an environment.
+    #[error("Failed to create environment: {0}")]
+    Environment(OrtApiError),
+    /// Could not check whether a value is a tensor.
+    #[error("Failed to check 'IsTensor': {0}")]
+    IsTensor(OrtApiError),
+    /// A value that was expected to be a tensor was not one.
+    #[error("Value is not a tensor")]
+    IsTensorCheck,
+    /// Could not append an execution provider to a session's options.
+    #[error("Failed to append execution provider: {0}")]
+    ExecutionProviderAppend(OrtApiError),
+    /// Could not query the `ONNXType` of a value.
+    #[error("Failed to get value type: {0}")]
+    GetValueType(OrtApiError),
+    /// Could not query the number of elements in a sequence.
+    #[error("Failed to get value count: {0}")]
+    GetValueCount(OrtApiError),
+    /// Could not retrieve an element of a sequence or map.
+    #[error("Failed to get value: {0}")]
+    GetValue(OrtApiError),
+    /// Encountered an `ONNXType` that is not a tensor, sequence, or map.
+    #[error("Unsupported ONNXType: {0}")]
+    UnsupportedOnnxType(String),
+    /// Called [`DynOrtValue::into_map`](crate::tensor::dyn_value::DynOrtValue::into_map) on a
+    /// non-map value.
+    #[error("DynOrtValue is not a Map")]
+    DynValueNotAMap,
+    /// Could not query a tensor's type and shape info.
+    #[error("Failed to get tensor type and shape: {0}")]
+    GetTensorTypeAndShape(OrtApiError),
+    /// Could not query the number of dimensions of a tensor's shape.
+    #[error("Failed to get dimensions count: {0}")]
+    GetDimensionsCount(OrtApiError),
+    /// Could not query a tensor's dimensions.
+    #[error("Failed to get dimensions: {0}")]
+    GetDimensions(OrtApiError),
+    /// Could not allocate a tensor `OrtValue`.
+    #[error("Failed to create tensor: {0}")]
+    CreateTensor(OrtApiError),
+    /// A `String` contained an interior NUL byte and could not be converted to a `CString`.
+    #[error("String contains an interior NUL byte")]
+    CStringNulError,
+    /// Could not fill a string tensor's contents.
+    #[error("Failed to fill string tensor: {0}")]
+    FillStringTensor(OrtApiError),
+    /// A tensor did not have the shape the caller expected (e.g. a loss tensor with no element).
+    #[error("Tensor does not have the expected shape")]
+    ShapeError,
+    /// Could not query the length of a string tensor's backing buffer.
+    #[error("Failed to get string tensor data length: {0}")]
+    GetStringTensorDataLength(OrtApiError),
+    /// Could not read a string tensor's backing buffer.
+    #[error("Failed to get string tensor content: {0}")]
+    GetStringTensorContent(OrtApiError),
+    /// A string tensor's backing buffer was not valid UTF-8.
+    #[error("String tensor content was not valid UTF-8")]
+    StringTensorUtf8,
+    /// Could not create an `OrtIoBinding`.
+    #[error("Failed to create IoBinding: {0}")]
+    CreateIoBinding(OrtApiError),
+    /// Could not bind an input value.
+    #[error("Failed to bind input: {0}")]
+    BindInput(OrtApiError),
+    /// Could not bind an output value.
+    #[error("Failed to bind output: {0}")]
+    BindOutput(OrtApiError),
+    /// Could not run a session against a set of bindings.
+    #[error("Failed to run with binding: {0}")]
+    RunWithBinding(OrtApiError),
+    /// Could not read the values bound to a binding's outputs.
+    #[error("Failed to get bound output values: {0}")]
+    GetBoundOutputValues(OrtApiError),
+    /// Could not run a session.
+    #[error("Failed to run session: {0}")]
+    Run(OrtApiError),
+    /// Could not read a session's model metadata.
+    #[error("Failed to get model metadata: {0}")]
+    SessionGetModelMetadata(OrtApiError),
+    /// Could not read a model's producer name.
+    #[error("Failed to get producer name: {0}")]
+    ModelMetadataGetProducerName(OrtApiError),
+    /// Could not read a model's graph name.
+    #[error("Failed to get graph name: {0}")]
+    ModelMetadataGetGraphName(OrtApiError),
+    /// Could not read a model's domain.
+    #[error("Failed to get domain: {0}")]
+    ModelMetadataGetDomain(OrtApiError),
+    /// Could not read a model's description.
+    #[error("Failed to get description: {0}")]
+    ModelMetadataGetDescription(OrtApiError),
+    /// Could not read a model's version.
+    #[error("Failed to get version: {0}")]
+    ModelMetadataGetVersion(OrtApiError),
+    /// Could not look up a key in a model's custom metadata map.
+    #[error("Failed to look up custom metadata: {0}")]
+    ModelMetadataLookupCustomMetadataMap(OrtApiError),
+    /// Could not read a model's custom metadata map keys.
+    #[error("Failed to get custom metadata keys: {0}")]
+    ModelMetadataGetCustomMetadataMapKeys(OrtApiError),
+    /// Could not add a session configuration entry.
+    #[error("Failed to add session config entry: {0}")]
+    AddSessionConfigEntry(OrtApiError),
+    /// Could not enable the bundled `onnxruntime-extensions` custom ops.
+    #[error("Failed to enable custom ops: {0}")]
+    EnableCustomOps(OrtApiError),
+    /// Could not register an external custom-ops library.
+    #[error("Failed to register custom ops library: {0}")]
+    RegisterCustomOpsLibrary(OrtApiError),
+    /// A path was not valid UTF-8.
+    #[error("Path is not valid UTF-8")]
+    NonUtf8Path,
+    /// Could not load a training checkpoint.
+    #[error("Failed to load checkpoint: {0}")]
+    LoadCheckpoint(OrtApiError),
+    /// Could not create a training session.
+    #[error("Failed to create training session: {0}")]
+    CreateTrainingSession(OrtApiError),
+    /// Could not run a training step.
+    #[error("Failed to run training step: {0}")]
+    TrainStep(OrtApiError),
+    /// Could not run an optimizer step.
+    #[error("Failed to run optimizer step: {0}")]
+    OptimizerStep(OrtApiError),
+    /// Could not reset accumulated gradients.
+    #[error("Failed to reset gradients: {0}")]
+    LazyResetGrad(OrtApiError),
+    /// Could not export an inference-only graph.
+    #[error("Failed to export model for inferencing: {0}")]
+    ExportModelForInferencing(OrtApiError),
+    /// The training API is unavailable because this build of ONNX Runtime was not built with
+    /// training support.
+    #[error("The ONNX Runtime training API is not available in this build")]
+    TrainingApiUnavailable,
+}
+
+/// Convert a raw `OrtStatus` returned by a C API call into a `Result`, releasing the status on
+/// the error path.
+pub(crate) fn status_to_result(
+    status: *mut sys::OrtStatus,
+) -> std::result::Result<(), OrtApiError> {
+    if status.is_null() {
+        return Ok(());
+    }
+
+    let raw_message = unsafe { g_ort().GetErrorMessage.unwrap()(status) };
+    let message = unsafe { CStr::from_ptr(raw_message) }
+        .to_string_lossy()
+        .into_owned();
+    unsafe { g_ort().ReleaseStatus.unwrap()(status) };
+
+    Err(OrtApiError { message })
+}
@@ -0,0 +1,298 @@
+//! Module containing execution provider types used to configure a [`SessionBuilder`](../session/struct.SessionBuilder.html).
+//!
+//! An [`ExecutionProvider`] is built into an [`ExecutionProviderDispatch`] and handed to
+//! [`SessionBuilder::with_execution_providers()`](../session/struct.SessionBuilder.html#method.with_execution_providers).
+//! Providers are registered on the session options in the order given; if a provider's shared
+//! library is not present on the host, registration for that provider is skipped (with a
+//! warning logged) and the next provider in the list is tried, eventually falling back to CPU.
+
+use onnxruntime_sys as sys;
+use tracing::warn;
+
+use crate::{
+    error::{status_to_result, OrtError},
+    g_ort,
+    session::SessionBuilder,
+    Result,
+};
+
+/// A provider-specific set of options that have been built and are ready to be appended to a
+/// session's [`sys::OrtSessionOptions`].
+///
+/// This is produced by calling [`ExecutionProvider::build()`] on one of the provider builders
+/// (e.g. [`CUDAExecutionProvider`]) and consumed by
+/// [`SessionBuilder::with_execution_providers()`](../session/struct.SessionBuilder.html#method.with_execution_providers).
+#[derive(Debug, Clone)]
+pub enum ExecutionProviderDispatch {
+    /// Dispatch for the CPU execution provider.
+    CPU(CPUExecutionProvider),
+    /// Dispatch for the CUDA execution provider.
+    CUDA(CUDAExecutionProvider),
+    /// Dispatch for the TensorRT execution provider.
+    TensorRT(TensorRTExecutionProvider),
+    /// Dispatch for the CoreML execution provider.
+    CoreML(CoreMLExecutionProvider),
+}
+
+impl ExecutionProviderDispatch {
+    fn name(&self) -> &'static str {
+        match self {
+            ExecutionProviderDispatch::CPU(_) => "CPU",
+            ExecutionProviderDispatch::CUDA(_) => "CUDA",
+            ExecutionProviderDispatch::TensorRT(_) => "TensorRT",
+            ExecutionProviderDispatch::CoreML(_) => "CoreML",
+        }
+    }
+
+    /// Attempt to register this provider on `options`.
+    ///
+    /// If the provider's shared library could not be located on the host, the failure is logged
+    /// and swallowed so the caller falls through to the next provider (eventually CPU). Any
+    /// other failure (bad device id, OOM, invalid arena config, ...) is a real configuration
+    /// error and is propagated.
+    pub(crate) fn append_to(&self, options: *mut sys::OrtSessionOptions) -> Result<()> {
+        let status = match self {
+            ExecutionProviderDispatch::CPU(provider) => unsafe {
+                g_ort().SessionOptionsAppendExecutionProvider_CPU.unwrap()(
+                    options,
+                    provider.use_arena as i32,
+                )
+            },
+            ExecutionProviderDispatch::CUDA(provider) => {
+                let cuda_options = sys::OrtCUDAProviderOptions {
+                    device_id: provider.device_id,
+                    arena_extend_strategy: provider.arena_extend_strategy,
+                    gpu_mem_limit: provider.gpu_mem_limit,
+                    ..Default::default()
+                };
+                unsafe {
+                    g_ort().SessionOptionsAppendExecutionProvider_CUDA.unwrap()(
+                        options,
+                        &cuda_options,
+                    )
+                }
+            }
+            ExecutionProviderDispatch::TensorRT(provider) => {
+                let trt_options = sys::OrtTensorRTProviderOptions {
+                    device_id: provider.device_id,
+                    trt_max_workspace_size: provider.max_workspace_size,
+                    trt_fp16_enable: provider.fp16_enable as i32,
+                    ..Default::default()
+                };
+                unsafe {
+                    g_ort()
+                        .SessionOptionsAppendExecutionProvider_TensorRT
+                        .unwrap()(options, &trt_options)
+                }
+            }
+            ExecutionProviderDispatch::CoreML(provider) => unsafe {
+                g_ort().SessionOptionsAppendExecutionProvider_CoreML.unwrap()(
+                    options,
+                    provider.flags,
+                )
+            },
+        };
+
+        match status_to_result(status) {
+            Ok(()) => Ok(()),
+            Err(e) if provider_library_missing(&e) => {
+                warn!(
+                    provider = self.name(),
+                    error = %e,
+                    "Execution provider's shared library is not available on this host, falling back."
+                );
+                Ok(())
+            }
+            Err(e) => Err(OrtError::ExecutionProviderAppend(e)),
+        }
+    }
+}
+
+/// ONNX Runtime reports a missing provider shared library (e.g. `onnxruntime_providers_cuda.so`
+/// not found next to the main library) as a regular error status, so the only way to recognize
+/// it is by sniffing the loader's own wording in the message.
+fn provider_library_missing(error: &crate::error::OrtApiError) -> bool {
+    let message = error.message().to_ascii_lowercase();
+    ["shared library", "dynamic library", "dlopen", "loadlibrary"]
+        .iter()
+        .any(|needle| message.contains(needle))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::OrtApiError;
+
+    #[test]
+    fn recognizes_missing_shared_library_messages() {
+        let err = OrtApiError::new(
+            "Failed to load library libonnxruntime_providers_cuda.so: dlopen failed",
+        );
+        assert!(provider_library_missing(&err));
+    }
+
+    #[test]
+    fn does_not_swallow_real_configuration_errors() {
+        let err = OrtApiError::new("device_id must be in range [0, num_devices)");
+        assert!(!provider_library_missing(&err));
+    }
+}
+
+/// Common trait implemented by every execution provider builder.
+///
+/// Calling [`build()`](ExecutionProvider::build) turns the builder into an
+/// [`ExecutionProviderDispatch`] that can be handed to
+/// [`SessionBuilder::with_execution_providers()`](../session/struct.SessionBuilder.html#method.with_execution_providers).
+pub trait ExecutionProvider {
+    /// Finalize the provider's options into a dispatchable value.
+    fn build(self) -> ExecutionProviderDispatch;
+}
+
+/// Options for the CUDA execution provider.
+#[derive(Debug, Clone, Default)]
+pub struct CUDAExecutionProvider {
+    device_id: i32,
+    arena_extend_strategy: i32,
+    gpu_mem_limit: usize,
+}
+
+impl CUDAExecutionProvider {
+    /// Select which CUDA device to run on (defaults to `0`).
+    #[must_use]
+    pub fn with_device_id(mut self, device_id: i32) -> Self {
+        self.device_id = device_id;
+        self
+    }
+
+    /// Configure the arena extend strategy (`0` = next power of two, `1` = same as requested).
+    #[must_use]
+    pub fn with_arena_extend_strategy(mut self, strategy: i32) -> Self {
+        self.arena_extend_strategy = strategy;
+        self
+    }
+
+    /// Cap the GPU memory arena at `limit` bytes (`0` means unlimited).
+    #[must_use]
+    pub fn with_gpu_mem_limit(mut self, limit: usize) -> Self {
+        self.gpu_mem_limit = limit;
+        self
+    }
+}
+
+impl ExecutionProvider for CUDAExecutionProvider {
+    fn build(self) -> ExecutionProviderDispatch {
+        ExecutionProviderDispatch::CUDA(self)
+    }
+}
+
+/// Options for the TensorRT execution provider.
+#[derive(Debug, Clone, Default)]
+pub struct TensorRTExecutionProvider {
+    device_id: i32,
+    max_workspace_size: usize,
+    fp16_enable: bool,
+}
+
+impl TensorRTExecutionProvider {
+    /// Select which CUDA device TensorRT should run on.
+    #[must_use]
+    pub fn with_device_id(mut self, device_id: i32) -> Self {
+        self.device_id = device_id;
+        self
+    }
+
+    /// Set the maximum workspace size, in bytes, TensorRT may use for its engine build.
+    #[must_use]
+    pub fn with_max_workspace_size(mut self, bytes: usize) -> Self {
+        self.max_workspace_size = bytes;
+        self
+    }
+
+    /// Enable FP16 precision for the TensorRT engine.
+    #[must_use]
+    pub fn with_fp16_enable(mut self, enable: bool) -> Self {
+        self.fp16_enable = enable;
+        self
+    }
+}
+
+impl ExecutionProvider for TensorRTExecutionProvider {
+    fn build(self) -> ExecutionProviderDispatch {
+        ExecutionProviderDispatch::TensorRT(self)
+    }
+}
+
+/// Options for the CoreML execution provider (macOS/iOS only).
+#[derive(Debug, Clone, Default)]
+pub struct CoreMLExecutionProvider {
+    flags: u32,
+}
+
+impl CoreMLExecutionProvider {
+    /// Set the raw `COREMLFlags` bitmask passed to the provider.
+    #[must_use]
+    pub fn with_flags(mut self, flags: u32) -> Self {
+        self.flags = flags;
+        self
+    }
+}
+
+impl ExecutionProvider for CoreMLExecutionProvider {
+    fn build(self) -> ExecutionProviderDispatch {
+        ExecutionProviderDispatch::CoreML(self)
+    }
+}
+
+/// Options for the default CPU execution provider.
+#[derive(Debug, Clone, Default)]
+pub struct CPUExecutionProvider {
+    use_arena: bool,
+}
+
+impl CPUExecutionProvider {
+    /// Whether the CPU allocator should use an arena (defaults to `false`).
+    #[must_use]
+    pub fn with_arena_allocator(mut self, use_arena: bool) -> Self {
+        self.use_arena = use_arena;
+        self
+    }
+}
+
+impl ExecutionProvider for CPUExecutionProvider {
+    fn build(self) -> ExecutionProviderDispatch {
+        ExecutionProviderDispatch::CPU(self)
+    }
+}
+
+impl SessionBuilder {
+    /// Register execution providers on this session, in priority order.
+    ///
+    /// Providers are tried in the order given; if a provider's shared library is not present on
+    /// the host the registration is skipped with a warning and the next provider is tried. The
+    /// session always keeps the built-in CPU execution provider as the final fallback, so a
+    /// model built with e.g. `[CUDAExecutionProvider::default().build()]` will transparently run
+    /// on CPU on a machine without a GPU.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::error::Error;
+    /// # use onnxruntime::{environment::Environment, execution_providers::CUDAExecutionProvider};
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let environment = Environment::builder().build()?;
+    /// let session_builder = environment
+    ///     .new_session_builder()?
+    ///     .with_execution_providers([CUDAExecutionProvider::default().with_device_id(0).build()])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_execution_providers(
+        self,
+        execution_providers: impl IntoIterator<Item = ExecutionProviderDispatch>,
+    ) -> Result<SessionBuilder> {
+        for provider in execution_providers {
+            provider.append_to(self.session_options_ptr)?;
+        }
+        Ok(self)
+    }
+}
@@ -0,0 +1,202 @@
+//! Module containing [`IoBinding`], which lets a [`Session`](session/struct.Session.html) be run
+//! against pre-placed device tensors instead of re-copying inputs/outputs on every call.
+//!
+//! For iterative or streaming inference (especially on a GPU execution provider), creating a
+//! fresh `OrtValue` per input and copying outputs back to the host on every
+//! [`Session::run()`](session/struct.Session.html#method.run) call is wasteful. `IoBinding` binds
+//! named inputs/outputs once against a session and can then be run repeatedly with `run()`.
+
+use std::{ffi::CString, fmt::Debug};
+
+use onnxruntime_sys as sys;
+
+use crate::{
+    error::status_to_result,
+    g_ort,
+    memory::MemoryInfo,
+    session::Session,
+    tensor::{construct::ConstructTensor, ort_output_tensor::OrtOwnedTensorExtractor, OrtOutputTensor},
+    OrtError, Result,
+};
+
+/// A set of input/output bindings for a [`Session`], allowing repeated `run()` calls to reuse
+/// pre-placed device tensors instead of round-tripping through the host on every call.
+#[derive(Debug)]
+pub struct IoBinding<'s> {
+    pub(crate) binding_ptr: *mut sys::OrtIoBinding,
+    session: &'s Session,
+    output_names: Vec<String>,
+    /// Names bound via `bind_output_to_value`, whose `OrtValue` is already owned by the caller.
+    /// `GetBoundOutputValues` hands back the same pointer for these names, so `outputs()` must
+    /// not wrap it in another owning `OrtOutputTensor` or the value would be double-freed.
+    caller_owned_outputs: std::collections::HashSet<String>,
+    /// The `OrtInputTensor`s built by `bind_input`, kept alive for as long as the binding is:
+    /// `BindInput` only hands the C API a pointer to the `OrtValue`, it does not take ownership
+    /// of it, so dropping the `OrtInputTensor` right after binding would release (numeric inputs:
+    /// free the caller's own backing buffer out from under) the value before `run()` ever uses
+    /// it. The type is erased to `dyn Debug` since each `bind_input::<T>` call can bind a
+    /// different `T`.
+    bound_inputs: Vec<Box<dyn Debug>>,
+}
+
+impl<'s> IoBinding<'s> {
+    pub(crate) fn new(session: &'s Session) -> Result<IoBinding<'s>> {
+        let mut binding_ptr: *mut sys::OrtIoBinding = std::ptr::null_mut();
+        let status =
+            unsafe { g_ort().CreateIoBinding.unwrap()(session.session_ptr, &mut binding_ptr) };
+        status_to_result(status).map_err(OrtError::CreateIoBinding)?;
+
+        Ok(IoBinding {
+            binding_ptr,
+            session,
+            output_names: Vec::new(),
+            caller_owned_outputs: std::collections::HashSet::new(),
+            bound_inputs: Vec::new(),
+        })
+    }
+
+    /// Bind `value` as the input named `name`, using `session`'s default memory info and
+    /// allocator to construct the underlying `OrtValue`.
+    pub fn bind_input<T>(&mut self, name: &str, value: T) -> Result<()>
+    where
+        T: ConstructTensor + Debug + 'static,
+    {
+        let cname = CString::new(name).map_err(|_| OrtError::CStringNulError)?;
+
+        let memory_info = MemoryInfo::cpu(sys::OrtAllocatorType::OrtArenaAllocator)?;
+        let input = value.construct(&memory_info, self.session.allocator_ptr)?;
+
+        let status = unsafe {
+            g_ort().BindInput.unwrap()(self.binding_ptr, cname.as_ptr(), input.tensor_ptr)
+        };
+        status_to_result(status).map_err(OrtError::BindInput)?;
+
+        // Keep the `OrtValue` (and, for numeric inputs, its backing buffer) alive until the
+        // binding itself is dropped.
+        self.bound_inputs.push(Box::new(input));
+
+        Ok(())
+    }
+
+    /// Bind the output named `name` to device memory described by `memory_info`, letting the
+    /// runtime allocate the backing tensor on `run()`.
+    pub fn bind_output(&mut self, name: &str, memory_info: &MemoryInfo) -> Result<()> {
+        let cname = CString::new(name).map_err(|_| OrtError::CStringNulError)?;
+
+        let status = unsafe {
+            g_ort().BindOutputToDevice.unwrap()(
+                self.binding_ptr,
+                cname.as_ptr(),
+                memory_info.ptr,
+            )
+        };
+        status_to_result(status).map_err(OrtError::BindOutput)?;
+
+        self.output_names.push(name.to_string());
+        Ok(())
+    }
+
+    /// Bind the output named `name` directly to a pre-allocated `OrtOutputTensor`, e.g. a
+    /// persistent CUDA buffer reused across `run()` calls.
+    ///
+    /// `value` stays owned by the caller: [`outputs()`](IoBinding::outputs) will not return a
+    /// tensor for `name`, since `GetBoundOutputValues` would hand back the very same `OrtValue`
+    /// and wrapping it in a second owning `OrtOutputTensor` would release it twice.
+    pub fn bind_output_to_value(&mut self, name: &str, value: &OrtOutputTensor) -> Result<()> {
+        let cname = CString::new(name).map_err(|_| OrtError::CStringNulError)?;
+
+        let status = unsafe {
+            g_ort().BindOutput.unwrap()(self.binding_ptr, cname.as_ptr(), value.tensor_ptr)
+        };
+        status_to_result(status).map_err(OrtError::BindOutput)?;
+
+        self.output_names.push(name.to_string());
+        self.caller_owned_outputs.insert(name.to_string());
+        Ok(())
+    }
+
+    /// Run the session against the currently bound inputs and outputs.
+    pub fn run(&mut self) -> Result<()> {
+        let run_options: *const sys::OrtRunOptions = std::ptr::null();
+        let status = unsafe {
+            g_ort().RunWithBinding.unwrap()(
+                self.session.session_ptr,
+                run_options,
+                self.binding_ptr,
+            )
+        };
+        status_to_result(status).map_err(OrtError::RunWithBinding)?;
+
+        Ok(())
+    }
+
+    /// Retrieve the tensors currently bound to the output side, in the order they were bound,
+    /// skipping names that were bound via
+    /// [`bind_output_to_value()`](IoBinding::bind_output_to_value) (the caller already owns
+    /// those `OrtValue`s).
+    ///
+    /// Call this after [`run()`](IoBinding::run) to read results for outputs that were bound
+    /// with [`bind_output()`](IoBinding::bind_output) (whose backing memory the runtime
+    /// allocates lazily).
+    pub fn outputs(&mut self) -> Result<Vec<OrtOutputTensor>> {
+        let mut values_ptr: *mut *mut sys::OrtValue = std::ptr::null_mut();
+        let mut count = 0usize;
+        let status = unsafe {
+            g_ort().GetBoundOutputValues.unwrap()(
+                self.binding_ptr,
+                self.session.allocator_ptr,
+                &mut values_ptr,
+                &mut count,
+            )
+        };
+        status_to_result(status).map_err(OrtError::GetBoundOutputValues)?;
+
+        let values = unsafe { std::slice::from_raw_parts(values_ptr, count) };
+
+        // `GetBoundOutputValues` returns one `OrtValue*` per name in `self.output_names`, in
+        // binding order. For a name bound through `bind_output_to_value` that pointer is the very
+        // one the caller already owns, so it's skipped here rather than wrapped in a second
+        // owning `OrtOutputTensor`, which would release it twice.
+        let tensors = self
+            .output_names
+            .iter()
+            .zip(values.iter())
+            .filter(|(name, _)| !self.caller_owned_outputs.contains(*name))
+            .map(|(_, tensor_ptr)| {
+                let shape = crate::tensor::dyn_value::tensor_shape(*tensor_ptr)?;
+                OrtOwnedTensorExtractor {
+                    tensor_ptr: *tensor_ptr,
+                    shape,
+                }
+                .extract()
+            })
+            .collect();
+
+        // `GetBoundOutputValues` allocates the `*OrtValue` array itself (as opposed to the
+        // `OrtValue`s it points to, which each `OrtOutputTensor` above now owns and releases);
+        // that array has to be freed separately through the same allocator or it leaks on every
+        // call.
+        unsafe {
+            ((*self.session.allocator_ptr).Free.unwrap())(
+                self.session.allocator_ptr,
+                values_ptr.cast::<std::ffi::c_void>(),
+            )
+        };
+
+        tensors
+    }
+}
+
+impl<'s> Drop for IoBinding<'s> {
+    fn drop(&mut self) {
+        unsafe { g_ort().ReleaseIoBinding.unwrap()(self.binding_ptr) };
+        self.binding_ptr = std::ptr::null_mut();
+    }
+}
+
+impl Session {
+    /// Create an [`IoBinding`] for this session.
+    pub fn bind(&self) -> Result<IoBinding> {
+        IoBinding::new(self)
+    }
+}
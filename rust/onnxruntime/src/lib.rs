@@ -0,0 +1,159 @@
+//! Rust wrapper for Microsoft's [ONNX Runtime](https://github.com/microsoft/onnxruntime).
+
+pub mod allocator;
+pub mod environment;
+pub mod error;
+pub mod execution_providers;
+pub mod io_binding;
+pub mod memory;
+pub mod metadata;
+pub mod session;
+pub mod session_config;
+pub mod tensor;
+#[cfg(feature = "training")]
+pub mod training;
+
+use std::os::raw::c_char;
+
+use lazy_static::lazy_static;
+use onnxruntime_sys as sys;
+
+pub use error::{OrtError, Result};
+
+lazy_static! {
+    static ref G_ORT_API: &'static sys::OrtApi =
+        unsafe { &*sys::OrtGetApiBase().as_ref().unwrap().GetApi.unwrap()(sys::ORT_API_VERSION) };
+}
+
+/// Return the process-wide `OrtApi` function table.
+pub(crate) fn g_ort() -> &'static sys::OrtApi {
+    &G_ORT_API
+}
+
+/// The logging level used by an [`Environment`](environment::Environment).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoggingLevel {
+    /// Print all log messages, including detailed trace information.
+    Verbose,
+    /// Print informational messages.
+    Info,
+    /// Print warning messages and above.
+    Warning,
+    /// Print error messages and above.
+    Error,
+    /// Print only fatal messages.
+    Fatal,
+}
+
+impl From<LoggingLevel> for sys::OrtLoggingLevel {
+    fn from(level: LoggingLevel) -> Self {
+        match level {
+            LoggingLevel::Verbose => sys::OrtLoggingLevel::ORT_LOGGING_LEVEL_VERBOSE,
+            LoggingLevel::Info => sys::OrtLoggingLevel::ORT_LOGGING_LEVEL_INFO,
+            LoggingLevel::Warning => sys::OrtLoggingLevel::ORT_LOGGING_LEVEL_WARNING,
+            LoggingLevel::Error => sys::OrtLoggingLevel::ORT_LOGGING_LEVEL_ERROR,
+            LoggingLevel::Fatal => sys::OrtLoggingLevel::ORT_LOGGING_LEVEL_FATAL,
+        }
+    }
+}
+
+/// The optimization level applied to a model's graph when a session is built.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphOptimizationLevel {
+    /// Disable all graph optimizations.
+    DisableAll,
+    /// Apply optimizations that only consider a single node at a time.
+    Basic,
+    /// Apply optimizations that consider multiple nodes at a time.
+    Extended,
+    /// Apply all available optimizations.
+    All,
+}
+
+impl From<GraphOptimizationLevel> for sys::GraphOptimizationLevel {
+    fn from(level: GraphOptimizationLevel) -> Self {
+        match level {
+            GraphOptimizationLevel::DisableAll => {
+                sys::GraphOptimizationLevel::ORT_DISABLE_ALL
+            }
+            GraphOptimizationLevel::Basic => sys::GraphOptimizationLevel::ORT_ENABLE_BASIC,
+            GraphOptimizationLevel::Extended => {
+                sys::GraphOptimizationLevel::ORT_ENABLE_EXTENDED
+            }
+            GraphOptimizationLevel::All => sys::GraphOptimizationLevel::ORT_ENABLE_ALL,
+        }
+    }
+}
+
+/// Custom logger passed to `CreateEnvWithCustomLogger`, forwarding ONNX Runtime's own log
+/// messages through `tracing`.
+pub(crate) extern "C" fn custom_logger(
+    _param: *mut std::ffi::c_void,
+    severity: sys::OrtLoggingLevel,
+    category: *const c_char,
+    _logid: *const c_char,
+    _code_location: *const c_char,
+    message: *const c_char,
+) {
+    let category = unsafe { std::ffi::CStr::from_ptr(category) }.to_string_lossy();
+    let message = unsafe { std::ffi::CStr::from_ptr(message) }.to_string_lossy();
+
+    match severity {
+        sys::OrtLoggingLevel::ORT_LOGGING_LEVEL_VERBOSE => {
+            tracing::trace!(%category, "{}", message)
+        }
+        sys::OrtLoggingLevel::ORT_LOGGING_LEVEL_INFO => tracing::info!(%category, "{}", message),
+        sys::OrtLoggingLevel::ORT_LOGGING_LEVEL_WARNING => {
+            tracing::warn!(%category, "{}", message)
+        }
+        sys::OrtLoggingLevel::ORT_LOGGING_LEVEL_ERROR
+        | sys::OrtLoggingLevel::ORT_LOGGING_LEVEL_FATAL => {
+            tracing::error!(%category, "{}", message)
+        }
+    }
+}
+
+/// Maps a Rust type to the `ONNXTensorElementDataType` it is represented by in a tensor.
+pub trait TypeToTensorElementDataType {
+    /// The ONNX Runtime tensor element type this Rust type corresponds to.
+    fn tensor_element_data_type() -> sys::ONNXTensorElementDataType;
+}
+
+/// Marker for the [`TypeToTensorElementDataType`] implementors that are backed by a plain,
+/// fixed-size buffer and so can go through `CreateTensorWithDataAsOrtValue`. `String` also
+/// implements `TypeToTensorElementDataType`, but its tensors are allocator- and
+/// `FillStringTensor`-backed instead, so it deliberately does not implement this trait too -
+/// that's what lets `tensor::construct::ConstructTensor` have one impl generic over this trait
+/// for numeric arrays and a separate impl specifically for `Array<String, D>` without the two
+/// overlapping.
+pub trait NumericTensorElementDataType: TypeToTensorElementDataType {}
+
+macro_rules! impl_type_to_tensor_element_data_type {
+    ($type_:ty, $variant:ident) => {
+        impl TypeToTensorElementDataType for $type_ {
+            fn tensor_element_data_type() -> sys::ONNXTensorElementDataType {
+                sys::ONNXTensorElementDataType::$variant
+            }
+        }
+
+        impl NumericTensorElementDataType for $type_ {}
+    };
+}
+
+impl_type_to_tensor_element_data_type!(f32, ONNX_TENSOR_ELEMENT_DATA_TYPE_FLOAT);
+impl_type_to_tensor_element_data_type!(f64, ONNX_TENSOR_ELEMENT_DATA_TYPE_DOUBLE);
+impl_type_to_tensor_element_data_type!(u8, ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT8);
+impl_type_to_tensor_element_data_type!(i8, ONNX_TENSOR_ELEMENT_DATA_TYPE_INT8);
+impl_type_to_tensor_element_data_type!(u16, ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT16);
+impl_type_to_tensor_element_data_type!(i16, ONNX_TENSOR_ELEMENT_DATA_TYPE_INT16);
+impl_type_to_tensor_element_data_type!(u32, ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT32);
+impl_type_to_tensor_element_data_type!(i32, ONNX_TENSOR_ELEMENT_DATA_TYPE_INT32);
+impl_type_to_tensor_element_data_type!(u64, ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT64);
+impl_type_to_tensor_element_data_type!(i64, ONNX_TENSOR_ELEMENT_DATA_TYPE_INT64);
+impl_type_to_tensor_element_data_type!(bool, ONNX_TENSOR_ELEMENT_DATA_TYPE_BOOL);
+
+impl TypeToTensorElementDataType for String {
+    fn tensor_element_data_type() -> sys::ONNXTensorElementDataType {
+        sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_STRING
+    }
+}
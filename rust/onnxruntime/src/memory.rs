@@ -0,0 +1,35 @@
+//! Module containing the `MemoryInfo` type describing where a tensor's data lives.
+
+use onnxruntime_sys as sys;
+
+use crate::{error::status_to_result, g_ort, OrtError, Result};
+
+/// Describes the device and allocator a tensor's backing memory is associated with.
+#[derive(Debug)]
+pub struct MemoryInfo {
+    pub(crate) ptr: *mut sys::OrtMemoryInfo,
+}
+
+impl MemoryInfo {
+    /// A `MemoryInfo` describing CPU memory allocated with the given allocator type.
+    pub fn cpu(allocator_type: sys::OrtAllocatorType) -> Result<MemoryInfo> {
+        let mut ptr: *mut sys::OrtMemoryInfo = std::ptr::null_mut();
+        let status = unsafe {
+            g_ort().CreateCpuMemoryInfo.unwrap()(
+                allocator_type,
+                sys::OrtMemType::OrtMemTypeDefault,
+                &mut ptr,
+            )
+        };
+        status_to_result(status).map_err(OrtError::Environment)?;
+
+        Ok(MemoryInfo { ptr })
+    }
+}
+
+impl Drop for MemoryInfo {
+    fn drop(&mut self) {
+        unsafe { g_ort().ReleaseMemoryInfo.unwrap()(self.ptr) };
+        self.ptr = std::ptr::null_mut();
+    }
+}
@@ -0,0 +1,181 @@
+//! Module containing [`Metadata`], a reader for a loaded model's embedded metadata.
+
+use std::ffi::CStr;
+
+use onnxruntime_sys as sys;
+
+use crate::{
+    allocator::default_allocator, error::status_to_result, g_ort, session::Session, OrtError,
+    Result,
+};
+
+/// A loaded model's metadata, obtained via [`Session::metadata()`](../session/struct.Session.html#method.metadata).
+///
+/// Useful for serving pipelines that need to dispatch on information embedded in the model
+/// itself, such as a model version or preprocessing parameters stored under a custom key.
+#[derive(Debug)]
+pub struct Metadata {
+    metadata_ptr: *mut sys::OrtModelMetadata,
+}
+
+impl Metadata {
+    pub(crate) fn new(session: &Session) -> Result<Metadata> {
+        let mut metadata_ptr: *mut sys::OrtModelMetadata = std::ptr::null_mut();
+        let status = unsafe {
+            g_ort().SessionGetModelMetadata.unwrap()(session.session_ptr, &mut metadata_ptr)
+        };
+        status_to_result(status).map_err(OrtError::SessionGetModelMetadata)?;
+
+        Ok(Metadata { metadata_ptr })
+    }
+
+    /// The name of the tool that produced the model (e.g. `pytorch`, `skl2onnx`).
+    pub fn producer_name(&self) -> Result<String> {
+        self.string_field(
+            g_ort().ModelMetadataGetProducerName.unwrap(),
+            OrtError::ModelMetadataGetProducerName,
+        )
+    }
+
+    /// The name of the model's graph.
+    pub fn graph_name(&self) -> Result<String> {
+        self.string_field(
+            g_ort().ModelMetadataGetGraphName.unwrap(),
+            OrtError::ModelMetadataGetGraphName,
+        )
+    }
+
+    /// The model's domain (e.g. `ai.onnx`).
+    pub fn domain(&self) -> Result<String> {
+        self.string_field(
+            g_ort().ModelMetadataGetDomain.unwrap(),
+            OrtError::ModelMetadataGetDomain,
+        )
+    }
+
+    /// A free-form description of the model.
+    pub fn description(&self) -> Result<String> {
+        self.string_field(
+            g_ort().ModelMetadataGetDescription.unwrap(),
+            OrtError::ModelMetadataGetDescription,
+        )
+    }
+
+    /// The model's version number, as set by its producer.
+    pub fn version(&self) -> Result<i64> {
+        let mut version = 0i64;
+        let status = unsafe {
+            g_ort().ModelMetadataGetVersion.unwrap()(self.metadata_ptr, &mut version)
+        };
+        status_to_result(status).map_err(OrtError::ModelMetadataGetVersion)?;
+        Ok(version)
+    }
+
+    /// Look up a single entry in the model's custom metadata map, returning `Ok(None)` if `key`
+    /// is not present.
+    pub fn custom_metadata(&self, key: &str) -> Result<Option<String>> {
+        let ckey = std::ffi::CString::new(key).map_err(|_| OrtError::CStringNulError)?;
+        let mut value_ptr: *mut std::os::raw::c_char = std::ptr::null_mut();
+
+        let status = unsafe {
+            g_ort().ModelMetadataLookupCustomMetadataMap.unwrap()(
+                self.metadata_ptr,
+                default_allocator(),
+                ckey.as_ptr(),
+                &mut value_ptr,
+            )
+        };
+        status_to_result(status).map_err(OrtError::ModelMetadataLookupCustomMetadataMap)?;
+
+        if value_ptr.is_null() {
+            return Ok(None);
+        }
+
+        let value = unsafe { CStr::from_ptr(value_ptr) }
+            .to_string_lossy()
+            .into_owned();
+        unsafe { release_allocated(value_ptr.cast()) };
+
+        Ok(Some(value))
+    }
+
+    /// All keys present in the model's custom metadata map.
+    pub fn custom_metadata_keys(&self) -> Result<Vec<String>> {
+        let mut keys_ptr: *mut *mut std::os::raw::c_char = std::ptr::null_mut();
+        let mut count = 0i64;
+
+        let status = unsafe {
+            g_ort().ModelMetadataGetCustomMetadataMapKeys.unwrap()(
+                self.metadata_ptr,
+                default_allocator(),
+                &mut keys_ptr,
+                &mut count,
+            )
+        };
+        status_to_result(status)
+            .map_err(OrtError::ModelMetadataGetCustomMetadataMapKeys)?;
+
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+
+        let keys = unsafe { std::slice::from_raw_parts(keys_ptr, count as usize) };
+        let keys = keys
+            .iter()
+            .map(|key_ptr| {
+                let key = unsafe { CStr::from_ptr(*key_ptr) }
+                    .to_string_lossy()
+                    .into_owned();
+                unsafe { release_allocated(key_ptr.cast::<std::os::raw::c_void>()) };
+                key
+            })
+            .collect();
+
+        unsafe { release_allocated(keys_ptr.cast()) };
+
+        Ok(keys)
+    }
+
+    fn string_field(
+        &self,
+        getter: unsafe extern "C" fn(
+            *const sys::OrtModelMetadata,
+            *mut sys::OrtAllocator,
+            *mut *mut std::os::raw::c_char,
+        ) -> *mut sys::OrtStatus,
+        err: fn(crate::error::OrtApiError) -> OrtError,
+    ) -> Result<String> {
+        let mut value_ptr: *mut std::os::raw::c_char = std::ptr::null_mut();
+        let status =
+            unsafe { getter(self.metadata_ptr, default_allocator(), &mut value_ptr) };
+        status_to_result(status).map_err(err)?;
+
+        let value = unsafe { CStr::from_ptr(value_ptr) }
+            .to_string_lossy()
+            .into_owned();
+        unsafe { release_allocated(value_ptr.cast()) };
+
+        Ok(value)
+    }
+}
+
+/// Release a buffer that was allocated by the default allocator on our behalf, as required by
+/// every `ModelMetadataGet*` accessor.
+unsafe fn release_allocated(ptr: *mut std::os::raw::c_void) {
+    let allocator = default_allocator();
+    ((*allocator).Free.unwrap())(allocator, ptr);
+}
+
+impl Drop for Metadata {
+    fn drop(&mut self) {
+        unsafe { g_ort().ReleaseModelMetadata.unwrap()(self.metadata_ptr) };
+        self.metadata_ptr = std::ptr::null_mut();
+    }
+}
+
+impl Session {
+    /// Read this session's loaded model metadata.
+    pub fn metadata(&self) -> Result<Metadata> {
+        Metadata::new(self)
+    }
+}
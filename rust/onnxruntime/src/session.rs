@@ -0,0 +1,322 @@
+//! Module containing session types
+
+use std::{convert::TryFrom, ffi::CString, fmt::Debug, path::Path};
+
+use onnxruntime_sys as sys;
+
+use crate::{
+    allocator::default_allocator,
+    environment::Environment,
+    error::status_to_result,
+    g_ort,
+    memory::MemoryInfo,
+    tensor::{construct::ConstructTensor, ort_output_tensor::OrtOwnedTensorExtractor, OrtOutputTensor},
+    GraphOptimizationLevel, OrtError, Result,
+};
+
+/// Read a name out of the session via `getter` (one of `SessionGetInputName`/
+/// `SessionGetOutputName`), which allocates the returned `char*` on `allocator` and leaves
+/// freeing it to the caller.
+unsafe fn session_io_name(
+    getter: unsafe extern "C" fn(
+        *const sys::OrtSession,
+        usize,
+        *mut sys::OrtAllocator,
+        *mut *mut std::os::raw::c_char,
+    ) -> *mut sys::OrtStatus,
+    session_ptr: *const sys::OrtSession,
+    index: usize,
+    allocator_ptr: *mut sys::OrtAllocator,
+) -> Result<String> {
+    let mut name_ptr: *mut std::os::raw::c_char = std::ptr::null_mut();
+    let status = getter(session_ptr, index, allocator_ptr, &mut name_ptr);
+    status_to_result(status).map_err(OrtError::Environment)?;
+
+    let name = std::ffi::CStr::from_ptr(name_ptr)
+        .to_string_lossy()
+        .into_owned();
+    ((*allocator_ptr).Free.unwrap())(allocator_ptr, name_ptr.cast::<std::ffi::c_void>());
+
+    Ok(name)
+}
+
+/// Read an input's or output's `(name, dimensions)` at `index`, via `name_getter` and
+/// `type_info_getter` (the matching `SessionGet{Input,Output}{Name,TypeInfo}` pair).
+unsafe fn session_io_metadata(
+    name_getter: unsafe extern "C" fn(
+        *const sys::OrtSession,
+        usize,
+        *mut sys::OrtAllocator,
+        *mut *mut std::os::raw::c_char,
+    ) -> *mut sys::OrtStatus,
+    type_info_getter: unsafe extern "C" fn(
+        *const sys::OrtSession,
+        usize,
+        *mut *mut sys::OrtTypeInfo,
+    ) -> *mut sys::OrtStatus,
+    session_ptr: *const sys::OrtSession,
+    index: usize,
+    allocator_ptr: *mut sys::OrtAllocator,
+) -> Result<Input> {
+    let name = session_io_name(name_getter, session_ptr, index, allocator_ptr)?;
+
+    let mut type_info_ptr: *mut sys::OrtTypeInfo = std::ptr::null_mut();
+    let status = type_info_getter(session_ptr, index, &mut type_info_ptr);
+    status_to_result(status).map_err(OrtError::Environment)?;
+
+    let mut tensor_info_ptr: *const sys::OrtTensorTypeAndShapeInfo = std::ptr::null();
+    let status =
+        g_ort().CastTypeInfoToTensorInfo.unwrap()(type_info_ptr, &mut tensor_info_ptr);
+    status_to_result(status).map_err(OrtError::GetTensorTypeAndShape)?;
+
+    let mut num_dims = 0;
+    let status = g_ort().GetDimensionsCount.unwrap()(tensor_info_ptr, &mut num_dims);
+    status_to_result(status).map_err(OrtError::GetDimensionsCount)?;
+
+    let mut dims = vec![0i64; num_dims as usize];
+    let status =
+        g_ort().GetDimensions.unwrap()(tensor_info_ptr, dims.as_mut_ptr(), num_dims);
+    status_to_result(status).map_err(OrtError::GetDimensions)?;
+
+    g_ort().ReleaseTypeInfo.unwrap()(type_info_ptr);
+
+    let dimensions = dims
+        .into_iter()
+        .map(|d| if d < 0 { None } else { Some(d as u32) })
+        .collect();
+
+    Ok(Input { name, dimensions })
+}
+
+/// A loaded ONNX model, ready to run inference.
+///
+/// Created from an [`Environment`] via
+/// [`Environment::new_session_builder()`](../environment/struct.Environment.html#method.new_session_builder)
+/// and [`SessionBuilder::with_model_from_file()`].
+#[derive(Debug)]
+pub struct Session {
+    pub(crate) session_ptr: *mut sys::OrtSession,
+    pub(crate) allocator_ptr: *mut sys::OrtAllocator,
+    /// The session's input metadata, in the order the model declares them.
+    pub inputs: Vec<Input>,
+    /// The session's output metadata, in the order the model declares them.
+    pub outputs: Vec<Output>,
+}
+
+/// Metadata describing one of a session's inputs or outputs.
+#[derive(Debug, Clone)]
+pub struct Input {
+    /// The name the model uses for this input.
+    pub name: String,
+    dimensions: Vec<Option<u32>>,
+}
+
+/// Metadata describing one of a session's outputs.
+pub type Output = Input;
+
+impl Input {
+    /// The shape of this input/output, as declared by the model. A dimension of `None` means the
+    /// model left that dimension symbolic (e.g. a dynamic batch size).
+    pub fn dimensions(&self) -> impl Iterator<Item = Option<u32>> + '_ {
+        self.dimensions.iter().copied()
+    }
+}
+
+impl Session {
+    /// Run inference, converting `inputs` into `OrtValue`s via [`ConstructTensor`] and the
+    /// session's outputs into `TOut` via `TryFrom<OrtOutputTensor>`.
+    pub fn run<TIn, TOut>(&self, inputs: Vec<TIn>) -> Result<Vec<TOut>>
+    where
+        TIn: ConstructTensor + Debug,
+        TOut: TryFrom<OrtOutputTensor, Error = OrtError>,
+    {
+        let memory_info = MemoryInfo::cpu(sys::OrtAllocatorType::OrtArenaAllocator)?;
+
+        let input_names = self
+            .inputs
+            .iter()
+            .map(|input| CString::new(input.name.as_str()).map_err(|_| OrtError::CStringNulError))
+            .collect::<Result<Vec<_>>>()?;
+        let input_name_ptrs: Vec<*const std::os::raw::c_char> =
+            input_names.iter().map(|n| n.as_ptr()).collect();
+
+        let output_names = self
+            .outputs
+            .iter()
+            .map(|output| {
+                CString::new(output.name.as_str()).map_err(|_| OrtError::CStringNulError)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let output_name_ptrs: Vec<*const std::os::raw::c_char> =
+            output_names.iter().map(|n| n.as_ptr()).collect();
+
+        let input_tensors = inputs
+            .into_iter()
+            .map(|input| input.construct(&memory_info, self.allocator_ptr))
+            .collect::<Result<Vec<_>>>()?;
+        let input_ptrs: Vec<*const sys::OrtValue> = input_tensors
+            .iter()
+            .map(|t| t.tensor_ptr as *const sys::OrtValue)
+            .collect();
+
+        let mut output_ptrs = vec![std::ptr::null_mut(); output_name_ptrs.len()];
+
+        let run_options: *const sys::OrtRunOptions = std::ptr::null();
+        let status = unsafe {
+            g_ort().Run.unwrap()(
+                self.session_ptr,
+                run_options,
+                input_name_ptrs.as_ptr(),
+                input_ptrs.as_ptr(),
+                input_ptrs.len(),
+                output_name_ptrs.as_ptr(),
+                output_name_ptrs.len(),
+                output_ptrs.as_mut_ptr(),
+            )
+        };
+        status_to_result(status).map_err(OrtError::Run)?;
+
+        output_ptrs
+            .into_iter()
+            .zip(self.outputs.iter())
+            .map(|(tensor_ptr, output)| {
+                let shape = output
+                    .dimensions()
+                    .map(|d| d.unwrap_or(1) as usize)
+                    .collect();
+                let tensor = OrtOwnedTensorExtractor { tensor_ptr, shape }.extract()?;
+                TOut::try_from(tensor)
+            })
+            .collect()
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        unsafe { g_ort().ReleaseSession.unwrap()(self.session_ptr) };
+        self.session_ptr = std::ptr::null_mut();
+    }
+}
+
+/// Builder used to configure and create a [`Session`].
+#[derive(Debug)]
+pub struct SessionBuilder {
+    pub(crate) session_options_ptr: *mut sys::OrtSessionOptions,
+    environment: Environment,
+}
+
+impl SessionBuilder {
+    pub(crate) fn new(environment: &Environment) -> Result<SessionBuilder> {
+        let mut session_options_ptr: *mut sys::OrtSessionOptions = std::ptr::null_mut();
+        let status =
+            unsafe { g_ort().CreateSessionOptions.unwrap()(&mut session_options_ptr) };
+        status_to_result(status).map_err(OrtError::Environment)?;
+
+        Ok(SessionBuilder {
+            session_options_ptr,
+            environment: environment.clone(),
+        })
+    }
+
+    /// Set the graph optimization level applied when the model is loaded.
+    pub fn with_graph_optimization_level(
+        self,
+        level: GraphOptimizationLevel,
+    ) -> Result<SessionBuilder> {
+        let status = unsafe {
+            g_ort().SetSessionGraphOptimizationLevel.unwrap()(
+                self.session_options_ptr,
+                level.into(),
+            )
+        };
+        status_to_result(status).map_err(OrtError::Environment)?;
+
+        Ok(self)
+    }
+
+    /// Set the number of threads used to parallelize execution within a single operator.
+    pub fn with_intra_op_num_threads(self, num_threads: i32) -> Result<SessionBuilder> {
+        let status = unsafe {
+            g_ort().SetIntraOpNumThreads.unwrap()(self.session_options_ptr, num_threads)
+        };
+        status_to_result(status).map_err(OrtError::Environment)?;
+
+        Ok(self)
+    }
+
+    /// Load a model from a file on disk and build the [`Session`].
+    pub fn with_model_from_file(self, path: impl AsRef<Path>) -> Result<Session> {
+        let cpath =
+            CString::new(path.as_ref().to_str().ok_or(OrtError::NonUtf8Path)?)
+                .map_err(|_| OrtError::CStringNulError)?;
+
+        let mut session_ptr: *mut sys::OrtSession = std::ptr::null_mut();
+        let status = unsafe {
+            g_ort().CreateSession.unwrap()(
+                self.environment.env().env_ptr,
+                cpath.as_ptr(),
+                self.session_options_ptr,
+                &mut session_ptr,
+            )
+        };
+        status_to_result(status).map_err(OrtError::Environment)?;
+
+        let allocator_ptr = default_allocator();
+
+        let mut num_inputs = 0;
+        let status =
+            unsafe { g_ort().SessionGetInputCount.unwrap()(session_ptr, &mut num_inputs) };
+        status_to_result(status).map_err(OrtError::Environment)?;
+        let inputs = (0..num_inputs)
+            .map(|i| unsafe {
+                session_io_metadata(
+                    g_ort().SessionGetInputName.unwrap(),
+                    g_ort().SessionGetInputTypeInfo.unwrap(),
+                    session_ptr,
+                    i,
+                    allocator_ptr,
+                )
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut num_outputs = 0;
+        let status =
+            unsafe { g_ort().SessionGetOutputCount.unwrap()(session_ptr, &mut num_outputs) };
+        status_to_result(status).map_err(OrtError::Environment)?;
+        let outputs = (0..num_outputs)
+            .map(|i| unsafe {
+                session_io_metadata(
+                    g_ort().SessionGetOutputName.unwrap(),
+                    g_ort().SessionGetOutputTypeInfo.unwrap(),
+                    session_ptr,
+                    i,
+                    allocator_ptr,
+                )
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Session {
+            session_ptr,
+            allocator_ptr,
+            inputs,
+            outputs,
+        })
+    }
+}
+
+impl Drop for SessionBuilder {
+    fn drop(&mut self) {
+        unsafe { g_ort().ReleaseSessionOptions.unwrap()(self.session_options_ptr) };
+        self.session_options_ptr = std::ptr::null_mut();
+    }
+}
+
+/// A freshly created, default-configured `OrtSessionOptions`, for callers (such as
+/// [`TrainingSession`](crate::training::TrainingSession)) that need session options without
+/// going through [`SessionBuilder`].
+pub(crate) fn default_session_options_ptr() -> Result<*mut sys::OrtSessionOptions> {
+    let mut session_options_ptr: *mut sys::OrtSessionOptions = std::ptr::null_mut();
+    let status = unsafe { g_ort().CreateSessionOptions.unwrap()(&mut session_options_ptr) };
+    status_to_result(status).map_err(OrtError::Environment)?;
+    Ok(session_options_ptr)
+}
@@ -0,0 +1,52 @@
+//! Module adding free-form session configuration and `onnxruntime-extensions` support to
+//! [`SessionBuilder`](session/struct.SessionBuilder.html).
+
+use std::ffi::CString;
+
+use crate::{error::status_to_result, g_ort, session::SessionBuilder, OrtError, Result};
+
+impl SessionBuilder {
+    /// Set an arbitrary, documented session configuration entry (e.g.
+    /// `session.dynamic_block_base`, `session.use_env_allocators`) that doesn't have a dedicated
+    /// builder method.
+    ///
+    /// See the ONNX Runtime `C_API` header and `onnxruntime_session_options_config_keys.h` for
+    /// the full list of recognised keys.
+    pub fn with_config_entry(self, key: &str, value: &str) -> Result<SessionBuilder> {
+        let ckey = CString::new(key).map_err(|_| OrtError::CStringNulError)?;
+        let cvalue = CString::new(value).map_err(|_| OrtError::CStringNulError)?;
+
+        let status = unsafe {
+            g_ort().AddSessionConfigEntry.unwrap()(
+                self.session_options_ptr,
+                ckey.as_ptr(),
+                cvalue.as_ptr(),
+            )
+        };
+        status_to_result(status).map_err(OrtError::AddSessionConfigEntry)?;
+
+        Ok(self)
+    }
+
+    /// Enable the built-in `onnxruntime-extensions` custom operators (tokenizers, text ops,
+    /// etc.) for models that depend on them.
+    pub fn with_extensions(self) -> Result<SessionBuilder> {
+        let status = unsafe { g_ort().EnableOrtCustomOps.unwrap()(self.session_options_ptr) };
+        status_to_result(status).map_err(OrtError::EnableCustomOps)?;
+
+        Ok(self)
+    }
+
+    /// Load custom operators from an external shared library at `path`, for custom-op libraries
+    /// built separately from `onnxruntime-extensions` (e.g. a user's own custom-op `.so`/`.dll`).
+    pub fn with_custom_ops_library(self, path: &str) -> Result<SessionBuilder> {
+        let cpath = CString::new(path).map_err(|_| OrtError::CStringNulError)?;
+
+        let status = unsafe {
+            g_ort().RegisterCustomOpsLibrary_V2.unwrap()(self.session_options_ptr, cpath.as_ptr())
+        };
+        status_to_result(status).map_err(OrtError::RegisterCustomOpsLibrary)?;
+
+        Ok(self)
+    }
+}
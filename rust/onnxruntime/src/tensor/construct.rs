@@ -1,8 +1,12 @@
 //! convert module has the trait for conversion of Inputs ConstructTensor.
 
 use crate::tensor::ort_input_tensor::OrtInputTensor;
-use crate::{memory::MemoryInfo, OrtError};
-use onnxruntime_sys::OrtAllocator;
+use crate::{
+    error::status_to_result, g_ort, memory::MemoryInfo, NumericTensorElementDataType, OrtError,
+};
+use ndarray::{Array, Dimension};
+use onnxruntime_sys::{self as sys, OrtAllocator};
+use std::ffi::CString;
 use std::fmt::Debug;
 
 /// The Input type for Rust onnxruntime Session::run
@@ -17,3 +21,157 @@ pub trait ConstructTensor {
     where
         Self: Sized + Debug;
 }
+
+/// Convert each string to an owned, NUL-terminated `CString`, erroring out if any of them
+/// contain an interior NUL byte (which a C string cannot represent).
+///
+/// Pulled out of the `ConstructTensor` impls below because it's the one piece of string-tensor
+/// construction that doesn't need the ONNX Runtime C API to exercise.
+fn strings_to_cstrings<'a>(
+    strings: impl IntoIterator<Item = &'a String>,
+) -> Result<Vec<CString>, OrtError> {
+    strings
+        .into_iter()
+        .map(|s| CString::new(s.as_str()).map_err(|_| OrtError::CStringNulError))
+        .collect()
+}
+
+/// Allocate a string tensor of `shape` on `allocator` and fill it with `strings`, in the order
+/// given.
+fn construct_string_tensor(
+    shape: &[i64],
+    strings: impl IntoIterator<Item = String>,
+    allocator: *mut OrtAllocator,
+) -> Result<*mut sys::OrtValue, OrtError> {
+    let strings: Vec<String> = strings.into_iter().collect();
+
+    let mut tensor_ptr: *mut sys::OrtValue = std::ptr::null_mut();
+    let status = unsafe {
+        g_ort().CreateTensorAsOrtValue.unwrap()(
+            allocator,
+            shape.as_ptr(),
+            shape.len(),
+            sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_STRING,
+            &mut tensor_ptr,
+        )
+    };
+    status_to_result(status).map_err(OrtError::CreateTensor)?;
+
+    let cstrings = strings_to_cstrings(strings.iter())?;
+    let ptrs: Vec<*const std::os::raw::c_char> = cstrings.iter().map(|s| s.as_ptr()).collect();
+
+    let status =
+        unsafe { g_ort().FillStringTensor.unwrap()(tensor_ptr, ptrs.as_ptr(), ptrs.len()) };
+    status_to_result(status).map_err(OrtError::FillStringTensor)?;
+
+    Ok(tensor_ptr)
+}
+
+impl<T, D> ConstructTensor for Array<T, D>
+where
+    T: NumericTensorElementDataType + Debug + Clone,
+    D: Dimension + Debug,
+{
+    /// Numeric tensors are backed directly by the array's own contiguous buffer via
+    /// `CreateTensorWithDataAsOrtValue` (a `MemoryInfo`, rather than an allocator, describes where
+    /// that buffer lives), so constructing one is zero-copy. The `Array` is kept alive inside the
+    /// returned `OrtInputTensor` for as long as the `OrtValue` referencing its buffer is in use.
+    fn construct(
+        mut self,
+        memory_info: &MemoryInfo,
+        _allocator: *mut OrtAllocator,
+    ) -> Result<OrtInputTensor<Self>, OrtError> {
+        let shape: Vec<i64> = self.shape().iter().map(|d| *d as i64).collect();
+        let data_len = self.len() * std::mem::size_of::<T>();
+
+        let mut tensor_ptr: *mut sys::OrtValue = std::ptr::null_mut();
+        let status = unsafe {
+            g_ort().CreateTensorWithDataAsOrtValue.unwrap()(
+                memory_info.ptr,
+                self.as_mut_ptr().cast::<std::ffi::c_void>(),
+                data_len,
+                shape.as_ptr(),
+                shape.len(),
+                T::tensor_element_data_type(),
+                &mut tensor_ptr,
+            )
+        };
+        status_to_result(status).map_err(OrtError::CreateTensor)?;
+
+        Ok(OrtInputTensor {
+            tensor_ptr,
+            array: self,
+        })
+    }
+}
+
+impl<D> ConstructTensor for Array<String, D>
+where
+    D: Dimension + Debug,
+{
+    /// String tensors are not backed by a contiguous raw-pointer buffer like numeric tensors, so
+    /// they're allocated with `CreateTensorAsOrtValue` (an allocator, rather than a
+    /// `MemoryInfo`-described buffer, owns the storage) and filled element-by-element with
+    /// `FillStringTensor`. Since the strings always live on the host, this works the same
+    /// regardless of which execution provider the session uses.
+    fn construct(
+        self,
+        _memory_info: &MemoryInfo,
+        allocator: *mut OrtAllocator,
+    ) -> Result<OrtInputTensor<Self>, OrtError> {
+        let shape: Vec<i64> = self.shape().iter().map(|d| *d as i64).collect();
+        let tensor_ptr =
+            construct_string_tensor(&shape, self.iter().cloned(), allocator)?;
+
+        Ok(OrtInputTensor {
+            tensor_ptr,
+            array: self,
+        })
+    }
+}
+
+impl ConstructTensor for Vec<String> {
+    /// Built directly rather than by delegating to the `Array<String, D>` impl: `OrtInputTensor`
+    /// releases its `OrtValue` on drop, so destructuring one to recover its `tensor_ptr` (as a
+    /// detour through `Array::from_shape_vec(...).construct(...)` would require) is a move out of
+    /// a `Drop` type and doesn't compile.
+    fn construct(
+        self,
+        _memory_info: &MemoryInfo,
+        allocator: *mut OrtAllocator,
+    ) -> Result<OrtInputTensor<Self>, OrtError> {
+        let shape = [self.len() as i64];
+        let tensor_ptr = construct_string_tensor(&shape, self.iter().cloned(), allocator)?;
+
+        Ok(OrtInputTensor {
+            tensor_ptr,
+            array: self,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strings_to_cstrings_preserves_content_and_order() {
+        let strings = vec!["hello".to_string(), "world".to_string(), "".to_string()];
+
+        let cstrings = strings_to_cstrings(strings.iter()).unwrap();
+
+        assert_eq!(
+            cstrings.iter().map(|s| s.to_str().unwrap()).collect::<Vec<_>>(),
+            vec!["hello", "world", ""]
+        );
+    }
+
+    #[test]
+    fn strings_to_cstrings_rejects_interior_nul() {
+        let strings = vec!["bad\0string".to_string()];
+
+        let err = strings_to_cstrings(strings.iter()).unwrap_err();
+
+        assert!(matches!(err, OrtError::CStringNulError));
+    }
+}
@@ -0,0 +1,241 @@
+//! Module containing [`DynOrtValue`], a type-erased ONNX Runtime output value.
+//!
+//! `Session::run()` returns dense tensors for most models, but models that end in a `ZipMap`
+//! (as produced by `skl2onnx` for scikit-learn classifiers) return a sequence of maps instead.
+//! [`DynOrtValue`] inspects the runtime `ONNXType` of each output and lets callers extract the
+//! shape that's actually there instead of forcing every output through the tensor-only path.
+
+use std::{collections::HashMap, hash::Hash};
+
+use onnxruntime_sys as sys;
+
+use std::{ffi::CString, fmt::Debug};
+
+use crate::{
+    error::status_to_result,
+    g_ort,
+    session::Session,
+    tensor::{construct::ConstructTensor, ort_output_tensor::{OrtOutputTensor, OrtOwnedTensorExtractor}},
+    OrtError, Result, TypeToTensorElementDataType,
+};
+
+/// A type-erased value returned from [`Session::run_dyn()`](../session/struct.Session.html#method.run_dyn).
+///
+/// Use [`TryFrom`] (via the existing `WithOutputTensor` conversions) on the [`DynOrtValue::Tensor`]
+/// variant to read dense tensor outputs, [`DynOrtValue::into_map`] for `ZipMap`-style classifier
+/// outputs, and pattern match on [`DynOrtValue::Sequence`] for `Seq<...>` outputs.
+#[derive(Debug)]
+pub enum DynOrtValue {
+    /// A dense tensor output.
+    Tensor(OrtOutputTensor),
+    /// A sequence of values, as produced by e.g. a `ZipMap` node wrapped in a `Sequence`.
+    Sequence(Vec<DynOrtValue>),
+    /// A single key/value map, as produced by a `ZipMap` node.
+    Map {
+        /// The map's keys, as a 1-D tensor.
+        keys: OrtOutputTensor,
+        /// The map's values, as a 1-D tensor with the same length as `keys`.
+        values: OrtOutputTensor,
+    },
+}
+
+impl DynOrtValue {
+    /// Inspect `value_ptr`'s `ONNXType` and build the matching [`DynOrtValue`] variant,
+    /// recursing into sequences and maps as needed.
+    ///
+    /// Takes ownership of `value_ptr`; the resulting value is responsible for releasing it.
+    pub(crate) fn from_value_ptr(value_ptr: *mut sys::OrtValue) -> Result<DynOrtValue> {
+        let mut onnx_type = sys::ONNXType::ONNX_TYPE_UNKNOWN;
+        let status = unsafe { g_ort().GetValueType.unwrap()(value_ptr, &mut onnx_type) };
+        status_to_result(status).map_err(OrtError::GetValueType)?;
+
+        match onnx_type {
+            sys::ONNXType::ONNX_TYPE_TENSOR => {
+                let shape = tensor_shape(value_ptr)?;
+                Ok(DynOrtValue::Tensor(OrtOwnedTensorExtractor {
+                    tensor_ptr: value_ptr,
+                    shape,
+                }
+                .extract()?))
+            }
+            sys::ONNXType::ONNX_TYPE_SEQUENCE => {
+                let mut count = 0;
+                let status =
+                    unsafe { g_ort().GetValueCount.unwrap()(value_ptr, &mut count) };
+                status_to_result(status).map_err(OrtError::GetValueCount)?;
+
+                let elements = (0..count)
+                    .map(|i| {
+                        let mut element_ptr: *mut sys::OrtValue = std::ptr::null_mut();
+                        let status = unsafe {
+                            g_ort().GetValue.unwrap()(
+                                value_ptr,
+                                i as i32,
+                                crate::allocator::default_allocator(),
+                                &mut element_ptr,
+                            )
+                        };
+                        status_to_result(status).map_err(OrtError::GetValue)?;
+                        DynOrtValue::from_value_ptr(element_ptr)
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                unsafe { g_ort().ReleaseValue.unwrap()(value_ptr) };
+
+                Ok(DynOrtValue::Sequence(elements))
+            }
+            sys::ONNXType::ONNX_TYPE_MAP => {
+                let mut keys_ptr: *mut sys::OrtValue = std::ptr::null_mut();
+                let mut values_ptr: *mut sys::OrtValue = std::ptr::null_mut();
+
+                let status = unsafe {
+                    g_ort().GetValue.unwrap()(
+                        value_ptr,
+                        0,
+                        crate::allocator::default_allocator(),
+                        &mut keys_ptr,
+                    )
+                };
+                status_to_result(status).map_err(OrtError::GetValue)?;
+
+                let status = unsafe {
+                    g_ort().GetValue.unwrap()(
+                        value_ptr,
+                        1,
+                        crate::allocator::default_allocator(),
+                        &mut values_ptr,
+                    )
+                };
+                status_to_result(status).map_err(OrtError::GetValue)?;
+
+                let keys_shape = tensor_shape(keys_ptr)?;
+                let values_shape = tensor_shape(values_ptr)?;
+
+                unsafe { g_ort().ReleaseValue.unwrap()(value_ptr) };
+
+                Ok(DynOrtValue::Map {
+                    keys: OrtOwnedTensorExtractor {
+                        tensor_ptr: keys_ptr,
+                        shape: keys_shape,
+                    }
+                    .extract()?,
+                    values: OrtOwnedTensorExtractor {
+                        tensor_ptr: values_ptr,
+                        shape: values_shape,
+                    }
+                    .extract()?,
+                })
+            }
+            other => Err(OrtError::UnsupportedOnnxType(format!("{:?}", other))),
+        }
+    }
+
+    /// Consume a [`DynOrtValue::Map`] into an owned `HashMap<K, V>`.
+    ///
+    /// Returns [`OrtError::DynValueNotAMap`] if called on a `Tensor` or `Sequence` variant.
+    pub fn into_map<K, V>(self) -> Result<HashMap<K, V>>
+    where
+        K: TypeToTensorElementDataType + Eq + Hash + Clone,
+        V: TypeToTensorElementDataType + Clone,
+    {
+        use std::convert::TryFrom;
+
+        let (keys, values) = match self {
+            DynOrtValue::Map { keys, values } => (keys, values),
+            _ => return Err(OrtError::DynValueNotAMap),
+        };
+
+        let keys = crate::tensor::WithOutputTensor::<&[K]>::try_from(keys)?;
+        let values = crate::tensor::WithOutputTensor::<&[V]>::try_from(values)?;
+
+        Ok(keys.iter().cloned().zip(values.iter().cloned()).collect())
+    }
+}
+
+pub(crate) fn tensor_shape(value_ptr: *mut sys::OrtValue) -> Result<Vec<usize>> {
+    let mut type_and_shape: *mut sys::OrtTensorTypeAndShapeInfo = std::ptr::null_mut();
+    let status =
+        unsafe { g_ort().GetTensorTypeAndShape.unwrap()(value_ptr, &mut type_and_shape) };
+    status_to_result(status).map_err(OrtError::GetTensorTypeAndShape)?;
+
+    let mut num_dims = 0;
+    let status =
+        unsafe { g_ort().GetDimensionsCount.unwrap()(type_and_shape, &mut num_dims) };
+    status_to_result(status).map_err(OrtError::GetDimensionsCount)?;
+
+    let mut dims = vec![0i64; num_dims as usize];
+    let status = unsafe {
+        g_ort().GetDimensions.unwrap()(type_and_shape, dims.as_mut_ptr(), num_dims)
+    };
+    status_to_result(status).map_err(OrtError::GetDimensions)?;
+
+    unsafe { g_ort().ReleaseTensorTypeAndShapeInfo.unwrap()(type_and_shape) };
+
+    Ok(dims.into_iter().map(|d| d as usize).collect())
+}
+
+impl Session {
+    /// Run inference like [`Session::run()`], but without committing to a tensor output type.
+    ///
+    /// Use this instead of [`Session::run()`] for models whose outputs aren't plain dense
+    /// tensors, e.g. a `ZipMap` classifier exported by `skl2onnx`, which returns a sequence of
+    /// maps. Each output is inspected by its runtime `ONNXType` and wrapped in the matching
+    /// [`DynOrtValue`] variant.
+    pub fn run_dyn<TIn>(&self, inputs: Vec<TIn>) -> Result<Vec<DynOrtValue>>
+    where
+        TIn: ConstructTensor + Debug,
+    {
+        let memory_info = crate::memory::MemoryInfo::cpu(
+            sys::OrtAllocatorType::OrtArenaAllocator,
+        )?;
+
+        let input_names = self
+            .inputs
+            .iter()
+            .map(|input| CString::new(input.name.as_str()).map_err(|_| OrtError::CStringNulError))
+            .collect::<Result<Vec<_>>>()?;
+        let input_name_ptrs: Vec<*const std::os::raw::c_char> =
+            input_names.iter().map(|n| n.as_ptr()).collect();
+
+        let output_names = self
+            .outputs
+            .iter()
+            .map(|output| {
+                CString::new(output.name.as_str()).map_err(|_| OrtError::CStringNulError)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let output_name_ptrs: Vec<*const std::os::raw::c_char> =
+            output_names.iter().map(|n| n.as_ptr()).collect();
+
+        let input_tensors = inputs
+            .into_iter()
+            .map(|input| input.construct(&memory_info, self.allocator_ptr))
+            .collect::<Result<Vec<_>>>()?;
+        let input_ptrs: Vec<*const sys::OrtValue> = input_tensors
+            .iter()
+            .map(|t| t.tensor_ptr as *const sys::OrtValue)
+            .collect();
+
+        let mut output_ptrs = vec![std::ptr::null_mut(); output_name_ptrs.len()];
+
+        let run_options: *const sys::OrtRunOptions = std::ptr::null();
+        let status = unsafe {
+            g_ort().Run.unwrap()(
+                self.session_ptr,
+                run_options,
+                input_name_ptrs.as_ptr(),
+                input_ptrs.as_ptr(),
+                input_ptrs.len(),
+                output_name_ptrs.as_ptr(),
+                output_name_ptrs.len(),
+                output_ptrs.as_mut_ptr(),
+            )
+        };
+        status_to_result(status).map_err(OrtError::Run)?;
+
+        output_ptrs
+            .into_iter()
+            .map(DynOrtValue::from_value_ptr)
+            .collect()
+    }
+}
@@ -0,0 +1,10 @@
+//! Module containing the tensor types used for `Session::run()` inputs and outputs.
+
+pub mod construct;
+pub mod dyn_value;
+pub mod ort_input_tensor;
+pub mod ort_output_tensor;
+
+pub use construct::ConstructTensor;
+pub use dyn_value::DynOrtValue;
+pub use ort_output_tensor::{OrtOutputTensor, WithOutputTensor};
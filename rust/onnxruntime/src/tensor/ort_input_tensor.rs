@@ -0,0 +1,31 @@
+//! Module containing the owned `OrtValue` built from a caller-supplied input.
+
+use std::fmt::Debug;
+
+use onnxruntime_sys as sys;
+
+use crate::g_ort;
+
+/// An input `OrtValue` built by [`ConstructTensor::construct`](super::construct::ConstructTensor::construct),
+/// together with the data it was built from.
+///
+/// For numeric element types the `OrtValue` is created directly over `array`'s buffer without
+/// copying, so `array` must be kept alive for as long as the `OrtValue` is in use; for string
+/// element types the runtime's own allocator owns the tensor's storage, but `array` is still kept
+/// around so the `OrtValue`'s lifetime and the input data's lifetime stay tied together either
+/// way.
+#[derive(Debug)]
+pub struct OrtInputTensor<T> {
+    pub(crate) tensor_ptr: *mut sys::OrtValue,
+    #[allow(dead_code)]
+    pub(crate) array: T,
+}
+
+impl<T> Drop for OrtInputTensor<T> {
+    fn drop(&mut self) {
+        unsafe { g_ort().ReleaseValue.unwrap()(self.tensor_ptr) };
+        self.tensor_ptr = std::ptr::null_mut();
+    }
+}
+
+unsafe impl<T: Debug> Send for OrtInputTensor<T> {}
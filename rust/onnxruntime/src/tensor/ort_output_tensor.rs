@@ -135,3 +135,92 @@ where
         })
     }
 }
+
+impl TryFrom<OrtOutputTensor> for WithOutputTensor<Vec<String>> {
+    type Error = OrtError;
+
+    /// String tensors don't expose a raw data pointer through `GetTensorMutableData`, so the
+    /// total byte length is read with `GetStringTensorDataLength`, the backing bytes are read with
+    /// `GetStringTensorContent` into one flat buffer, and that buffer is split into owned
+    /// `String`s using the per-element offsets `GetStringTensorContent` reports.
+    fn try_from(value: OrtOutputTensor) -> Result<Self> {
+        let length = value.shape.iter().fold(1, |acc, el| acc * el);
+
+        let mut content_length = 0usize;
+        let status = unsafe {
+            g_ort().GetStringTensorDataLength.unwrap()(value.tensor_ptr, &mut content_length)
+        };
+        status_to_result(status).map_err(OrtError::GetStringTensorDataLength)?;
+
+        let mut content = vec![0u8; content_length];
+        let mut offsets = vec![0usize; length];
+        let status = unsafe {
+            g_ort().GetStringTensorContent.unwrap()(
+                value.tensor_ptr,
+                content.as_mut_ptr().cast::<std::ffi::c_void>(),
+                content_length,
+                offsets.as_mut_ptr(),
+                offsets.len(),
+            )
+        };
+        status_to_result(status).map_err(OrtError::GetStringTensorContent)?;
+
+        let strings = strings_from_content(&content, &offsets)?;
+
+        Ok(WithOutputTensor {
+            tensor: value,
+            item: strings,
+        })
+    }
+}
+
+/// Split a string tensor's flat content buffer into owned `String`s using the per-element start
+/// offsets reported by `GetStringTensorContent`.
+///
+/// Pulled out of the `TryFrom` impl above because it's the one piece of string-tensor extraction
+/// that doesn't need the ONNX Runtime C API to exercise.
+fn strings_from_content(content: &[u8], offsets: &[usize]) -> Result<Vec<String>> {
+    let mut strings = Vec::with_capacity(offsets.len());
+    for i in 0..offsets.len() {
+        let start = offsets[i];
+        let end = offsets.get(i + 1).copied().unwrap_or(content.len());
+        strings.push(
+            String::from_utf8(content[start..end].to_vec())
+                .map_err(|_| OrtError::StringTensorUtf8)?,
+        );
+    }
+    Ok(strings)
+}
+
+#[cfg(test)]
+mod string_tensor_tests {
+    use super::strings_from_content;
+
+    #[test]
+    fn splits_content_at_offsets() {
+        let content = b"helloworldfoo";
+        let offsets = [0, 5, 10];
+
+        let strings = strings_from_content(content, &offsets).unwrap();
+
+        assert_eq!(strings, vec!["hello", "world", "foo"]);
+    }
+
+    #[test]
+    fn handles_empty_elements() {
+        let content = b"ab";
+        let offsets = [0, 0, 1, 2];
+
+        let strings = strings_from_content(content, &offsets).unwrap();
+
+        assert_eq!(strings, vec!["", "a", "b", ""]);
+    }
+
+    #[test]
+    fn rejects_invalid_utf8() {
+        let content = [0xff, 0xfe];
+        let offsets = [0];
+
+        assert!(strings_from_content(&content, &offsets).is_err());
+    }
+}
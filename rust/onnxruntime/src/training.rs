@@ -0,0 +1,227 @@
+//! Module containing [`TrainingSession`], a wrapper around ONNX Runtime's on-device training
+//! API (checkpoint state plus training/eval/optimizer graphs).
+//!
+//! Gated behind the `training` feature since it links against the training build of the ONNX
+//! Runtime C API, which most consumers of this crate don't need.
+
+use std::{convert::TryFrom, ffi::CString, fmt::Debug, path::Path};
+
+use onnxruntime_sys as sys;
+
+use crate::{
+    environment::Environment,
+    error::status_to_result,
+    g_ort,
+    memory::MemoryInfo,
+    tensor::{construct::ConstructTensor, ort_output_tensor::OrtOwnedTensorExtractor, OrtOutputTensor},
+    OrtError, Result,
+};
+
+fn path_to_cstring(path: &Path) -> Result<CString> {
+    let s = path.to_str().ok_or(OrtError::NonUtf8Path)?;
+    CString::new(s).map_err(|_| OrtError::CStringNulError)
+}
+
+/// An on-device training session: a checkpoint plus a training graph, an optional eval graph,
+/// and an optimizer graph.
+///
+/// Built once from an [`Environment`] and a set of model paths, then driven in a loop with
+/// [`train_step()`](TrainingSession::train_step),
+/// [`optimizer_step()`](TrainingSession::optimizer_step) and
+/// [`reset_grad()`](TrainingSession::reset_grad), the same shape as a typical PyTorch training
+/// loop. The reusable tensor conversions from [`crate::tensor`] back both the training inputs
+/// and the returned loss.
+#[derive(Debug)]
+pub struct TrainingSession {
+    training_session_ptr: *mut sys::OrtTrainingSession,
+    checkpoint_state_ptr: *mut sys::OrtCheckpointState,
+    #[allow(dead_code)]
+    environment: Environment,
+}
+
+impl TrainingSession {
+    /// Load a checkpoint and the training/eval/optimizer graphs that go with it.
+    ///
+    /// `eval_model_path` may be omitted if the training loop doesn't evaluate in-process.
+    pub fn new(
+        environment: &Environment,
+        checkpoint_path: impl AsRef<Path>,
+        training_model_path: impl AsRef<Path>,
+        eval_model_path: Option<impl AsRef<Path>>,
+        optimizer_model_path: impl AsRef<Path>,
+    ) -> Result<TrainingSession> {
+        let training_api = g_ort_training()?;
+
+        let mut checkpoint_state_ptr: *mut sys::OrtCheckpointState = std::ptr::null_mut();
+        let checkpoint_cpath = path_to_cstring(checkpoint_path.as_ref())?;
+        let status = unsafe {
+            training_api.LoadCheckpoint.unwrap()(
+                checkpoint_cpath.as_ptr(),
+                &mut checkpoint_state_ptr,
+            )
+        };
+        status_to_result(status).map_err(OrtError::LoadCheckpoint)?;
+
+        let training_cpath = path_to_cstring(training_model_path.as_ref())?;
+        let eval_cpath = eval_model_path
+            .as_ref()
+            .map(|p| path_to_cstring(p.as_ref()))
+            .transpose()?;
+        let optimizer_cpath = path_to_cstring(optimizer_model_path.as_ref())?;
+
+        let session_options_ptr = crate::session::default_session_options_ptr()?;
+
+        let mut training_session_ptr: *mut sys::OrtTrainingSession = std::ptr::null_mut();
+        let status = unsafe {
+            training_api.CreateTrainingSession.unwrap()(
+                environment.env().env_ptr,
+                session_options_ptr,
+                checkpoint_state_ptr,
+                training_cpath.as_ptr(),
+                eval_cpath
+                    .as_ref()
+                    .map_or(std::ptr::null(), |p| p.as_ptr()),
+                optimizer_cpath.as_ptr(),
+                &mut training_session_ptr,
+            )
+        };
+        status_to_result(status).map_err(OrtError::CreateTrainingSession)?;
+
+        Ok(TrainingSession {
+            training_session_ptr,
+            checkpoint_state_ptr,
+            environment: environment.clone(),
+        })
+    }
+
+    /// Run a single forward+backward pass over `inputs`, accumulating gradients, and return the
+    /// computed loss.
+    pub fn train_step<T>(&mut self, inputs: Vec<T>) -> Result<WithOutputTensorLoss>
+    where
+        T: ConstructTensor + Debug,
+    {
+        let training_api = g_ort_training()?;
+
+        let memory_info = MemoryInfo::cpu(sys::OrtAllocatorType::OrtArenaAllocator)?;
+        let allocator = crate::allocator::default_allocator();
+
+        let input_tensors = inputs
+            .into_iter()
+            .map(|input| input.construct(&memory_info, allocator))
+            .collect::<Result<Vec<_>>>()?;
+        let input_ptrs: Vec<*const sys::OrtValue> = input_tensors
+            .iter()
+            .map(|t| t.tensor_ptr as *const sys::OrtValue)
+            .collect();
+
+        let mut loss_ptr: *mut sys::OrtValue = std::ptr::null_mut();
+        let status = unsafe {
+            training_api.TrainStep.unwrap()(
+                self.training_session_ptr,
+                std::ptr::null(),
+                input_ptrs.len(),
+                input_ptrs.as_ptr(),
+                1,
+                &mut loss_ptr,
+            )
+        };
+        status_to_result(status).map_err(OrtError::TrainStep)?;
+
+        let shape = crate::tensor::dyn_value::tensor_shape(loss_ptr)?;
+        let loss = OrtOwnedTensorExtractor {
+            tensor_ptr: loss_ptr,
+            shape,
+        }
+        .extract()?;
+
+        Ok(WithOutputTensorLoss(loss))
+    }
+
+    /// Apply the optimizer step using the gradients accumulated by prior
+    /// [`train_step()`](TrainingSession::train_step) calls.
+    pub fn optimizer_step(&mut self) -> Result<()> {
+        let training_api = g_ort_training()?;
+        let status = unsafe {
+            training_api.OptimizerStep.unwrap()(self.training_session_ptr, std::ptr::null())
+        };
+        status_to_result(status).map_err(OrtError::OptimizerStep)?;
+        Ok(())
+    }
+
+    /// Zero out the accumulated gradients, ready for the next batch.
+    pub fn reset_grad(&mut self) -> Result<()> {
+        let training_api = g_ort_training()?;
+        let status =
+            unsafe { training_api.LazyResetGrad.unwrap()(self.training_session_ptr) };
+        status_to_result(status).map_err(OrtError::LazyResetGrad)?;
+        Ok(())
+    }
+
+    /// Save an inference-only graph (no gradient nodes) for the given `output_names` to `path`,
+    /// so the fine-tuned model can be loaded with a regular [`Session`](crate::session::Session).
+    pub fn export_model_for_inferencing(
+        &self,
+        path: impl AsRef<Path>,
+        output_names: &[&str],
+    ) -> Result<()> {
+        let training_api = g_ort_training()?;
+
+        let cpath = path_to_cstring(path.as_ref())?;
+        let coutput_names = output_names
+            .iter()
+            .map(|name| CString::new(*name).map_err(|_| OrtError::CStringNulError))
+            .collect::<Result<Vec<_>>>()?;
+        let output_name_ptrs: Vec<*const std::os::raw::c_char> =
+            coutput_names.iter().map(|n| n.as_ptr()).collect();
+
+        let status = unsafe {
+            training_api.ExportModelForInferencing.unwrap()(
+                self.training_session_ptr,
+                cpath.as_ptr(),
+                output_name_ptrs.len(),
+                output_name_ptrs.as_ptr(),
+            )
+        };
+        status_to_result(status).map_err(OrtError::ExportModelForInferencing)?;
+
+        Ok(())
+    }
+}
+
+/// The loss tensor returned by [`TrainingSession::train_step()`].
+#[derive(Debug)]
+pub struct WithOutputTensorLoss(pub(crate) OrtOutputTensor);
+
+impl WithOutputTensorLoss {
+    /// Read the scalar loss value out of the underlying tensor.
+    pub fn into_scalar(self) -> Result<f32> {
+        let loss = crate::tensor::WithOutputTensor::<&[f32]>::try_from(self.0)?;
+        loss.first().copied().ok_or(OrtError::ShapeError)
+    }
+}
+
+impl Drop for TrainingSession {
+    fn drop(&mut self) {
+        // `new()` already called `g_ort_training()` successfully to get this far, so the
+        // training API is known to be available here.
+        let training_api = g_ort_training().expect("training API was available in `new()`");
+        unsafe {
+            training_api.ReleaseTrainingSession.unwrap()(self.training_session_ptr);
+            training_api.ReleaseCheckpointState.unwrap()(self.checkpoint_state_ptr);
+        }
+        self.training_session_ptr = std::ptr::null_mut();
+        self.checkpoint_state_ptr = std::ptr::null_mut();
+    }
+}
+
+/// Accessor for the training-specific subset of the ONNX Runtime C API, obtained the same way
+/// [`g_ort()`](crate::g_ort) obtains the main `OrtApi`. Returns
+/// [`OrtError::TrainingApiUnavailable`] instead of dereferencing a null pointer when this build
+/// of ONNX Runtime wasn't compiled with training support.
+fn g_ort_training() -> Result<&'static sys::OrtTrainingApi> {
+    let api_ptr = unsafe { g_ort().GetTrainingApi.unwrap()(sys::ORT_API_VERSION) };
+    if api_ptr.is_null() {
+        return Err(OrtError::TrainingApiUnavailable);
+    }
+    Ok(unsafe { &*api_ptr })
+}
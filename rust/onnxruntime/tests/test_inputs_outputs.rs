@@ -2,10 +2,7 @@ use image::{imageops::FilterType, ImageBuffer, Luma, Pixel};
 use ndarray::Array;
 use ndarray::Ix4;
 use onnxruntime::tensor::WithOutputTensor;
-use onnxruntime::{
-    download::vision::DomainBasedImageClassification, environment::Environment, session::Session,
-    GraphOptimizationLevel, LoggingLevel,
-};
+use onnxruntime::{environment::Environment, session::Session, GraphOptimizationLevel, LoggingLevel};
 use std::path::Path;
 use test_log::test;
 
@@ -16,6 +13,11 @@ fn mnist_session() -> (Environment, Session) {
         .build()
         .unwrap();
 
+    let model_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("data")
+        .join("mnist.onnx");
+
     let session = environment
         .new_session_builder()
         .unwrap()
@@ -23,8 +25,8 @@ fn mnist_session() -> (Environment, Session) {
         .unwrap()
         .with_intra_op_num_threads(1)
         .unwrap()
-        .with_model_downloaded(DomainBasedImageClassification::Mnist)
-        .expect("Could not download model from file");
+        .with_model_from_file(model_path)
+        .expect("Could not load model from file");
 
     (environment, session)
 }